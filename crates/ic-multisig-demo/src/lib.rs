@@ -2,9 +2,27 @@
 
 use candid::{CandidType, Deserialize};
 use ic_cdk::export::Principal;
-use ic_multisig_voting::{Multisig, Proposal};
+use ic_multisig_voting::{
+    ApproveOutcome, Clock, GovernanceAction, GovernanceOutcome, GovernanceRequest, Multisig, Proposal,
+};
 use std::cell::RefCell;
 
+/// Default time a proposal stays open before it can no longer be approved: 7 days.
+const DEFAULT_PROPOSAL_TTL_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Default delay between a proposal reaching threshold and becoming executable: 1 day.
+const DEFAULT_EXECUTION_DELAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Clock backed by the replica's system time.
+#[derive(Clone, Debug, Default)]
+struct IcClock;
+
+impl Clock for IcClock {
+    fn now_nanos(&self) -> u64 {
+        ic_cdk::api::time()
+    }
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct Config {
     pub max_payload_size: u32,
@@ -15,14 +33,13 @@ pub struct Config {
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ActionPayload {
     SetConfig(Config),
-    AddOwner(Principal),
-    RemoveOwner(Principal),
-    ChangeThreshold(u8),
 }
 
 thread_local! {
-    static MULTISIG: RefCell<Multisig<ActionPayload>> =
-        RefCell::new(Multisig::new(vec![], 1));
+    static MULTISIG: RefCell<Multisig<ActionPayload, IcClock>> =
+        RefCell::new(Multisig::with_clock(
+            vec![], 1, IcClock, Some(DEFAULT_PROPOSAL_TTL_NANOS), Some(DEFAULT_EXECUTION_DELAY_NANOS),
+        ));
 
     static CONFIG: RefCell<Config> = RefCell::new(Config {
         max_payload_size: 1024,
@@ -33,7 +50,7 @@ thread_local! {
 
 /// Helper function to interact with multisig state
 fn with_multisig<F, R>(f: F) -> R
-where F: FnOnce(&mut Multisig<ActionPayload>) -> R {
+where F: FnOnce(&mut Multisig<ActionPayload, IcClock>) -> R {
     MULTISIG.with(|ms| f(&mut ms.borrow_mut()))
 }
 
@@ -47,7 +64,9 @@ where F: FnOnce(&mut Config) -> R {
 #[ic_cdk::init]
 fn init(owners: Vec<Principal>, threshold: u8) {
     MULTISIG.with(|ms| {
-        *ms.borrow_mut() = Multisig::new(owners, threshold);
+        *ms.borrow_mut() = Multisig::with_clock(
+            owners, threshold, IcClock, Some(DEFAULT_PROPOSAL_TTL_NANOS), Some(DEFAULT_EXECUTION_DELAY_NANOS),
+        );
     });
 }
 
@@ -61,50 +80,74 @@ fn propose_set_config(config: Config) -> u64 {
     })
 }
 
-/// Propose adding a new owner
+/// Propose adding a new owner, as a counted governance request with its own
+/// confirmation tally rather than a generic `ActionPayload`
 #[ic_cdk::update]
 fn propose_add_owner(new_owner: Principal) -> u64 {
     let caller = ic_cdk::caller();
     with_multisig(|ms| {
-        ms.propose(caller, ActionPayload::AddOwner(new_owner))
+        ms.propose_governance(caller, GovernanceAction::AddOwner(new_owner))
             .unwrap_or_else(|e| ic_cdk::trap(&e))
     })
 }
 
-/// Propose removing an existing owner
+/// Propose removing an existing owner, as a governance request
 #[ic_cdk::update]
 fn propose_remove_owner(owner: Principal) -> u64 {
     let caller = ic_cdk::caller();
     with_multisig(|ms| {
-        ms.propose(caller, ActionPayload::RemoveOwner(owner))
+        ms.propose_governance(caller, GovernanceAction::RemoveOwner(owner))
             .unwrap_or_else(|e| ic_cdk::trap(&e))
     })
 }
 
-/// Propose changing the approval threshold
+/// Propose changing the approval threshold, as a governance request
 #[ic_cdk::update]
 fn propose_change_threshold(new_threshold: u8) -> u64 {
     let caller = ic_cdk::caller();
     with_multisig(|ms| {
-        ms.propose(caller, ActionPayload::ChangeThreshold(new_threshold))
+        ms.propose_governance(caller, GovernanceAction::ChangeThreshold(new_threshold))
             .unwrap_or_else(|e| ic_cdk::trap(&e))
     })
 }
 
-/// Approve a proposal by ID
+/// Approve a governance request by ID. Once threshold is reached the
+/// underlying owner/threshold change is applied immediately.
 #[ic_cdk::update]
-fn approve(proposal_id: u64) {
+fn approve_governance(request_id: u64) -> GovernanceOutcome {
     let caller = ic_cdk::caller();
+    with_multisig(|ms| ms.approve_governance(caller, request_id))
+        .unwrap_or_else(|e| ic_cdk::trap(&e))
+}
+
+/// List all open (active, unexpired) governance requests
+#[ic_cdk::query]
+fn list_governance_requests() -> Vec<GovernanceRequest> {
+    with_multisig(|ms| ms.list_open_governance_requests().into_iter().cloned().collect())
+}
 
-    let result = with_multisig(|ms| ms.approve(caller, proposal_id));
+/// Approve a proposal by ID. Once threshold is reached the proposal is
+/// scheduled rather than executed immediately; the returned outcome carries
+/// the time at which `execute_proposal` may be called to run it.
+#[ic_cdk::update]
+fn approve(proposal_id: u64) -> ApproveOutcome {
+    let caller = ic_cdk::caller();
+
+    with_multisig(|ms| ms.approve(caller, proposal_id))
+        .unwrap_or_else(|e| ic_cdk::trap(&e))
+}
+
+/// Execute a proposal whose timelock has elapsed
+#[ic_cdk::update]
+fn execute_proposal(proposal_id: u64) {
+    let result = with_multisig(|ms| ms.execute(proposal_id));
 
     match result {
         Ok(Some(action)) => {
             execute_action(action);
         },
         Ok(None) => {
-            // Successfully voted, but threshold not yet reached
-            ic_cdk::println!("Vote recorded. Waiting for more approvals.");
+            ic_cdk::println!("Proposal is not ready to execute or was already executed.");
         },
         Err(e) => {
             ic_cdk::trap(&e);
@@ -112,6 +155,14 @@ fn approve(proposal_id: u64) {
     }
 }
 
+/// Revoke a previously cast approval on a pending proposal
+#[ic_cdk::update]
+fn revoke(proposal_id: u64) {
+    let caller = ic_cdk::caller();
+    with_multisig(|ms| ms.revoke_approval(caller, proposal_id))
+        .unwrap_or_else(|e| ic_cdk::trap(&e));
+}
+
 /// List all open (unexecuted) proposals
 #[ic_cdk::query]
 fn list_proposals() -> Vec<Proposal<ActionPayload>> {
@@ -148,33 +199,6 @@ fn execute_action(action: ActionPayload) {
             with_config(|cfg| *cfg = new_config);
             ic_cdk::println!("Configuration updated successfully");
         },
-        ActionPayload::AddOwner(new_owner) => {
-            with_multisig(|ms| {
-                if let Err(e) = ms.add_owner(new_owner) {
-                    ic_cdk::println!("Failed to add owner: {}", e);
-                } else {
-                    ic_cdk::println!("Owner {} added successfully", new_owner);
-                }
-            });
-        },
-        ActionPayload::RemoveOwner(owner) => {
-            with_multisig(|ms| {
-                if let Err(e) = ms.remove_owner(owner) {
-                    ic_cdk::println!("Failed to remove owner: {}", e);
-                } else {
-                    ic_cdk::println!("Owner {} removed successfully", owner);
-                }
-            });
-        },
-        ActionPayload::ChangeThreshold(new_threshold) => {
-            with_multisig(|ms| {
-                if let Err(e) = ms.set_threshold(new_threshold) {
-                    ic_cdk::println!("Failed to change threshold: {}", e);
-                } else {
-                    ic_cdk::println!("Threshold changed to {} successfully", new_threshold);
-                }
-            });
-        }
     }
 }
 
@@ -1,6 +1,6 @@
 // storage.rs - Storage trait and manager
-use crate::Multisig;
-use candid::{CandidType, Principal};
+use crate::{ApproveOutcome, GovernanceAction, GovernanceOutcome, Multisig};
+use candid::{CandidType, Deserialize, Principal};
 
 /// Trait for persisting multisig state
 pub trait MultisigStorage<T> {
@@ -37,7 +37,7 @@ pub struct MultisigManager<T, S: MultisigStorage<T>> {
 
 impl<T, S> MultisigManager<T, S>
 where
-    T: CandidType + Clone,
+    T: CandidType + Clone + for<'de> Deserialize<'de>,
     S: MultisigStorage<T>,
 {
     /// Create manager with custom storage backend
@@ -62,14 +62,54 @@ where
         Ok(result)
     }
 
+    /// Propose a bounded (preimage-backed) action with automatic persistence
+    pub fn propose_bounded(&mut self, caller: Principal, payload: T) -> Result<u64, String> {
+        let result = self.multisig.propose_bounded(caller, payload)?;
+        self.storage.save(&self.multisig)
+            .map_err(|_| "storage error".to_string())?;
+        Ok(result)
+    }
+
     /// Approve with automatic persistence
-    pub fn approve(&mut self, caller: Principal, id: u64) -> Result<Option<T>, String> {
+    pub fn approve(&mut self, caller: Principal, id: u64) -> Result<ApproveOutcome, String> {
         let result = self.multisig.approve(caller, id)?;
         self.storage.save(&self.multisig)
             .map_err(|_| "storage error".to_string())?;
         Ok(result)
     }
 
+    /// Execute a scheduled proposal with automatic persistence
+    pub fn execute(&mut self, id: u64) -> Result<Option<T>, String> {
+        let result = self.multisig.execute(id)?;
+        self.storage.save(&self.multisig)
+            .map_err(|_| "storage error".to_string())?;
+        Ok(result)
+    }
+
+    /// Revoke a previously cast approval, with automatic persistence
+    pub fn revoke_approval(&mut self, caller: Principal, id: u64) -> Result<(), String> {
+        self.multisig.revoke_approval(caller, id)?;
+        self.storage.save(&self.multisig)
+            .map_err(|_| "storage error".to_string())?;
+        Ok(())
+    }
+
+    /// Propose a governance request with automatic persistence
+    pub fn propose_governance(&mut self, caller: Principal, action: GovernanceAction) -> Result<u64, String> {
+        let result = self.multisig.propose_governance(caller, action)?;
+        self.storage.save(&self.multisig)
+            .map_err(|_| "storage error".to_string())?;
+        Ok(result)
+    }
+
+    /// Approve a governance request with automatic persistence
+    pub fn approve_governance(&mut self, caller: Principal, id: u64) -> Result<GovernanceOutcome, String> {
+        let result = self.multisig.approve_governance(caller, id)?;
+        self.storage.save(&self.multisig)
+            .map_err(|_| "storage error".to_string())?;
+        Ok(result)
+    }
+
     /// Direct access to multisig for queries (no persistence needed)
     pub fn multisig(&self) -> &Multisig<T> {
         &self.multisig
@@ -94,7 +134,7 @@ where
     }
 }
 
-impl<T: CandidType + Clone> MultisigManager<T, NoStorage> {
+impl<T: CandidType + Clone + for<'de> Deserialize<'de>> MultisigManager<T, NoStorage> {
     /// Create manager with no persistence (pure in-memory)
     pub fn in_memory(owners: Vec<Principal>, threshold: u8) -> Self {
         Self {
@@ -102,4 +142,59 @@ impl<T: CandidType + Clone> MultisigManager<T, NoStorage> {
             storage: NoStorage,
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_propose_approve_execute_round_trip() {
+        let owner1 = Principal::anonymous();
+        let owner2 = Principal::from_slice(&[1, 2, 3, 4]);
+        let mut manager = MultisigManager::<u32, NoStorage>::in_memory(vec![owner1, owner2], 2);
+
+        let id = manager.propose(owner1, 42).unwrap();
+        assert_eq!(manager.approve(owner1, id).unwrap(), ApproveOutcome::Pending);
+        assert!(matches!(manager.approve(owner2, id).unwrap(), ApproveOutcome::Scheduled { .. }));
+
+        let result = manager.execute(id).unwrap();
+        assert_eq!(result, Some(42));
+    }
+
+    #[test]
+    fn test_propose_bounded_round_trip() {
+        let owner1 = Principal::anonymous();
+        let owner2 = Principal::from_slice(&[1, 2, 3, 4]);
+        let mut manager = MultisigManager::<String, NoStorage>::in_memory(vec![owner1, owner2], 2);
+
+        let id = manager.propose_bounded(owner1, "a large config payload".to_string()).unwrap();
+        manager.approve(owner2, id).unwrap();
+        let result = manager.execute(id).unwrap();
+        assert_eq!(result, Some("a large config payload".to_string()));
+    }
+
+    #[test]
+    fn test_revoke_approval() {
+        let owner1 = Principal::anonymous();
+        let owner2 = Principal::from_slice(&[1, 2, 3, 4]);
+        let mut manager = MultisigManager::<u32, NoStorage>::in_memory(vec![owner1, owner2], 2);
+
+        let id = manager.propose(owner1, 42).unwrap();
+        manager.revoke_approval(owner1, id).unwrap();
+        assert_eq!(manager.multisig().get_proposal(id).unwrap().approvals.len(), 0);
+    }
+
+    #[test]
+    fn test_governance_add_owner() {
+        let owner1 = Principal::anonymous();
+        let owner2 = Principal::from_slice(&[1, 2, 3, 4]);
+        let new_owner = Principal::from_slice(&[5, 6, 7, 8]);
+        let mut manager = MultisigManager::<u32, NoStorage>::in_memory(vec![owner1, owner2], 2);
+
+        let id = manager.propose_governance(owner1, GovernanceAction::AddOwner(new_owner)).unwrap();
+        assert_eq!(manager.approve_governance(owner1, id).unwrap(), GovernanceOutcome::Pending);
+        assert_eq!(manager.approve_governance(owner2, id).unwrap(), GovernanceOutcome::Executed);
+        assert!(manager.multisig().get_owners().contains(&new_owner));
+    }
 }
\ No newline at end of file